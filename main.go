@@ -2,12 +2,22 @@ package main
 
 import (
 	"KdnLang/src/lexer"
+	"fmt"
 	"os"
 )
 
 func main() {
-	bytes, _ := os.ReadFile("test.lang")
-	tokens := lexer.Tokenize(string(bytes))
+	path := "test.lang"
+	bytes, err := os.ReadFile(path)
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "%s: error: could not read source file: %v\n", path, err)
+		os.Exit(1)
+	}
+	tokens, err := lexer.Tokenize(string(bytes))
+	if err != nil {
+		fmt.Fprintln(os.Stderr, err.(*lexer.LexError).Render(path))
+		os.Exit(1)
+	}
 
 	for _, token := range tokens {
 		token.Debug()
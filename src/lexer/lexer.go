@@ -3,6 +3,9 @@ package lexer
 import (
 	"fmt"
 	"regexp"
+	"strconv"
+	"strings"
+	"unicode/utf8"
 )
 
 type regexHandler func(lex *lexer, regex *regexp.Regexp)
@@ -12,11 +15,46 @@ type regexPattern struct {
 	handler regexHandler
 }
 
+const byteOrderMark = '\uFEFF'
+
+type confusable struct {
+	name        string
+	replacement string
+}
+
+// Characters that sneak in when code is pasted from documents or rich text editors
+var confusables = map[rune]confusable{
+	'\u00A0': {"non-breaking space", "a regular space"},
+	'\u201C': {"left smart quote", `a straight double quote (")`},
+	'\u201D': {"right smart quote", `a straight double quote (")`},
+	'\u2018': {"left smart single quote", "a straight single quote (')"},
+	'\u2019': {"right smart single quote", "a straight single quote (')"},
+	'\u2212': {"minus sign", "a hyphen (-)"},
+}
+
+// LexError is the first problem found in the source, positioned by 1-based line and column
+type LexError struct {
+	Line    int
+	Col     int
+	Offset  int // byte offset in the source, matching Token.Start
+	Message string
+}
+
+func (err *LexError) Error() string {
+	return fmt.Sprintf("%d:%d: %s", err.Line, err.Col, err.Message)
+}
+
+// Render formats the error as a single grep-able line, `path:line:col: error: message`
+func (err *LexError) Render(path string) string {
+	return fmt.Sprintf("%s:%d:%d: error: %s", path, err.Line, err.Col, err.Message)
+}
+
 type lexer struct {
 	patterns []regexPattern
 	Tokens   []Token
 	source   string
 	pos      int
+	err      *LexError
 }
 
 func (lex *lexer) advanceN(n int) {
@@ -39,9 +77,42 @@ func (lex *lexer) remainder() string {
 	return lex.source[lex.pos:]
 }
 
-func Tokenize(source string) []Token {
+// lineCol converts a byte offset into the source to a 1-based line and column
+func (lex *lexer) lineCol(offset int) (int, int) {
+	line, col := 1, 1
+	for i, ch := range lex.source[:offset] {
+		if i == 0 && ch == byteOrderMark {
+			// Editors don't display the byte order mark, so it takes up no column
+			continue
+		}
+		if ch == '\n' {
+			line++
+			col = 1
+		} else {
+			col++
+		}
+	}
+	return line, col
+}
+
+// fail records an error at the given byte offset, keeping only the first one reported
+func (lex *lexer) fail(offset int, message string) {
+	if lex.err == nil {
+		line, col := lex.lineCol(offset)
+		lex.err = &LexError{Line: line, Col: col, Offset: offset, Message: message}
+	}
+}
+
+// Tokenize lexes the whole source, any error it returns is a *LexError
+func Tokenize(source string) ([]Token, error) {
 	lex := createLexer(source)
 
+	// Some Windows editors prefix UTF-8 files with a byte order mark. Skipping it rather than
+	// trimming it keeps token offsets relative to the caller's source
+	if strings.HasPrefix(source, string(byteOrderMark)) {
+		lex.advanceN(utf8.RuneLen(byteOrderMark))
+	}
+
 	// iterate while we still have tokens
 	for !lex.at_eof() {
 		matched := false
@@ -56,20 +127,33 @@ func Tokenize(source string) []Token {
 			}
 		}
 
-		// Extend this in the future
+		if lex.err != nil {
+			return nil, lex.err
+		}
+
+		// Nothing matched, so name the offending byte or character as precisely as possible
 		if !matched {
-			panic(fmt.Sprintf("Lexer::Error -> unrecognised token near: %s\n", lex.remainder()))
+			ch, size := utf8.DecodeRuneInString(lex.remainder())
+			if ch == utf8.RuneError && size == 1 {
+				lex.fail(lex.pos, fmt.Sprintf("invalid UTF-8 byte 0x%02X", lex.at()))
+			} else if c, exists := confusables[ch]; exists {
+				lex.fail(lex.pos, fmt.Sprintf("unexpected %s (U+%04X), replace it with %s", c.name, ch, c.replacement))
+			} else {
+				lex.fail(lex.pos, fmt.Sprintf("unrecognised character %q (U+%04X)", ch, ch))
+			}
+			return nil, lex.err
 		}
 	}
-	lex.push(NewToken(EOF, "EOF"))
-	return lex.Tokens
+	lex.push(NewToken(EOF, "EOF", lex.pos, lex.pos))
+	return lex.Tokens, nil
 }
 
 func defaultHandler(kind TokenKind, value string) regexHandler {
 	return func(lex *lexer, regex *regexp.Regexp) {
+		start := lex.pos
 		// Advance the lexer's position past the value reached
 		lex.advanceN(len(value))
-		lex.push(NewToken(kind, value))
+		lex.push(NewToken(kind, value, start, lex.pos))
 	}
 }
 
@@ -82,7 +166,9 @@ func createLexer(source string) *lexer {
 		patterns: []regexPattern{
 			{regexp.MustCompile(`\s+`), skipHandler},
 			{regexp.MustCompile(`//.*`), skipHandler},
-			{regexp.MustCompile(`"[^"]*"`), stringHandler},
+			{regexp.MustCompile(`/\*`), blockCommentHandler},
+			{regexp.MustCompile(`(?s)"(\\.|[^"\\])*"`), stringHandler},
+			{regexp.MustCompile(`"`), unterminatedStringHandler},
 			{regexp.MustCompile(`[0-9]+(\.[0-9]+)?`), numberHandler},
 			{regexp.MustCompile(`[a-zA-Z_][a-zA-Z0-9_]*`), symbolHandler},
 			{regexp.MustCompile(`\[`), defaultHandler(OPEN_BRACKET, "[")},
@@ -93,6 +179,7 @@ func createLexer(source string) *lexer {
 			{regexp.MustCompile(`\)`), defaultHandler(CLOSE_PAREN, ")")},
 			{regexp.MustCompile(`==`), defaultHandler(EQUALS, "==")},
 			{regexp.MustCompile(`!=`), defaultHandler(NOT_EQUALS, "!=")},
+			{regexp.MustCompile(`=>`), defaultHandler(FAT_ARROW, "=>")},
 			{regexp.MustCompile(`=`), defaultHandler(ASSIGNMENT, "=")},
 			{regexp.MustCompile(`!`), defaultHandler(NOT, "!")},
 			{regexp.MustCompile(`<=`), defaultHandler(LESS_EQUALS, "<=")},
@@ -101,6 +188,7 @@ func createLexer(source string) *lexer {
 			{regexp.MustCompile(`>`), defaultHandler(GREATER, ">")},
 			{regexp.MustCompile(`\|\|`), defaultHandler(OR, "||")},
 			{regexp.MustCompile(`&&`), defaultHandler(AND, "&&")},
+			{regexp.MustCompile(`\.\.=`), defaultHandler(DOT_DOT_EQUALS, "..=")},
 			{regexp.MustCompile(`\.\.`), defaultHandler(DOT_DOT, "..")},
 			{regexp.MustCompile(`\.`), defaultHandler(DOT, ".")},
 			{regexp.MustCompile(`;`), defaultHandler(SEMI_COLON, ";")},
@@ -112,6 +200,9 @@ func createLexer(source string) *lexer {
 			{regexp.MustCompile(`--`), defaultHandler(MINUS_MINUS, "--")},
 			{regexp.MustCompile(`\+=`), defaultHandler(PLUS_EQUALS, "+=")},
 			{regexp.MustCompile(`-=`), defaultHandler(MINUS_EQUALS, "-=")},
+			{regexp.MustCompile(`\*=`), defaultHandler(STAR_EQUALS, "*=")},
+			{regexp.MustCompile(`/=`), defaultHandler(SLASH_EQUALS, "/=")},
+			{regexp.MustCompile(`%=`), defaultHandler(PERCENT_EQUALS, "%=")},
 			{regexp.MustCompile(`\+`), defaultHandler(PLUS, "+")},
 			{regexp.MustCompile(`-`), defaultHandler(DASH, "-")},
 			{regexp.MustCompile(`/`), defaultHandler(SLASH, "/")},
@@ -125,18 +216,70 @@ func symbolHandler(lex *lexer, regex *regexp.Regexp) {
 	value := regex.FindString(lex.remainder())
 
 	if kind, exists := reserved_lu[value]; exists {
-		lex.push(NewToken(kind, value))
+		lex.push(NewToken(kind, value, lex.pos, lex.pos+len(value)))
 	} else {
-		lex.push(NewToken(IDENTIFIER, value))
+		lex.push(NewToken(IDENTIFIER, value, lex.pos, lex.pos+len(value)))
 	}
 	lex.advanceN(len(value))
 }
 
 func stringHandler(lex *lexer, regex *regexp.Regexp) {
-	match := regex.FindStringIndex(lex.remainder())
-	stringLiteral := lex.remainder()[match[0]+1 : match[1]-1]
-	lex.push(NewToken(STRING, stringLiteral))
-	lex.advanceN(match[1] + 2)
+	match := regex.FindString(lex.remainder())
+	stringLiteral := lex.unescape(match[1:len(match)-1], lex.pos+1)
+	if lex.err != nil {
+		return
+	}
+	lex.push(NewToken(STRING, stringLiteral, lex.pos, lex.pos+len(match)))
+	lex.advanceN(len(match))
+}
+
+// Only reached when the string pattern fails, meaning the literal never closes
+func unterminatedStringHandler(lex *lexer, regex *regexp.Regexp) {
+	lex.fail(lex.pos, "unterminated string literal")
+}
+
+// unescape resolves the escape sequences in a string literal body found at offset in the source
+func (lex *lexer) unescape(body string, offset int) string {
+	var builder strings.Builder
+
+	for i := 0; i < len(body); i++ {
+		if body[i] != '\\' {
+			builder.WriteByte(body[i])
+			continue
+		}
+
+		// The string pattern guarantees a character follows every backslash
+		i++
+		switch body[i] {
+		case 'n':
+			builder.WriteByte('\n')
+		case 't':
+			builder.WriteByte('\t')
+		case 'r':
+			builder.WriteByte('\r')
+		case '\\', '"', '\'':
+			builder.WriteByte(body[i])
+		case 'u':
+			end := strings.IndexByte(body[i:], '}')
+			if i+1 >= len(body) || body[i+1] != '{' || end == -1 {
+				lex.fail(offset+i-1, "malformed unicode escape, expected \\u{XXXX}")
+				return ""
+			}
+			code, err := strconv.ParseUint(body[i+2:i+end], 16, 32)
+			if err != nil || !utf8.ValidRune(rune(code)) {
+				lex.fail(offset+i-1, fmt.Sprintf("invalid unicode escape '\\u{%s}'", body[i+2:i+end]))
+				return ""
+			}
+			builder.WriteRune(rune(code))
+			i += end
+		default:
+			escaped, _ := utf8.DecodeRuneInString(body[i:])
+			lex.fail(offset+i-1, fmt.Sprintf("unknown escape sequence, backslash followed by %q (U+%04X)", escaped, escaped))
+			return ""
+		}
+	}
+
+	return builder.String()
 }
 
 func skipHandler(lex *lexer, regex *regexp.Regexp) {
@@ -144,8 +287,50 @@ func skipHandler(lex *lexer, regex *regexp.Regexp) {
 	lex.advanceN(match[1])
 }
 
+// Block comments nest, so commenting out code that already contains one keeps working
+func blockCommentHandler(lex *lexer, regex *regexp.Regexp) {
+	depth := 0
+	for i := lex.pos; i+1 < len(lex.source); i++ {
+		switch lex.source[i : i+2] {
+		case "/*":
+			depth++
+			i++
+		case "*/":
+			depth--
+			i++
+			if depth == 0 {
+				lex.advanceN(i + 1 - lex.pos)
+				return
+			}
+		}
+	}
+	lex.fail(lex.pos, "unterminated block comment")
+}
+
 func numberHandler(lex *lexer, regex *regexp.Regexp) {
 	match := regex.FindString(lex.remainder())
-	lex.push(NewToken(NUMBER, match))
+	end := lex.pos + len(match)
+
+	// `1.2.3` is never a valid literal, while `1.2..3` is a float followed by a range
+	if strings.Contains(match, ".") && end+1 < len(lex.source) && lex.source[end] == '.' && isDigit(lex.source[end+1]) {
+		lex.fail(end, "unexpected second decimal point")
+		return
+	}
+
+	// `123abc` would otherwise lex as a number followed by an identifier
+	if end < len(lex.source) && isIdentifierStart(lex.source[end]) {
+		lex.fail(end, fmt.Sprintf("unexpected character '%c' after number", lex.source[end]))
+		return
+	}
+
+	lex.push(NewToken(NUMBER, match, lex.pos, end))
 	lex.advanceN(len(match))
 }
+
+func isDigit(ch byte) bool {
+	return ch >= '0' && ch <= '9'
+}
+
+func isIdentifierStart(ch byte) bool {
+	return ch == '_' || (ch >= 'a' && ch <= 'z') || (ch >= 'A' && ch <= 'Z')
+}
@@ -0,0 +1,260 @@
+package lexer
+
+import (
+	"errors"
+	"reflect"
+	"testing"
+)
+
+type tokenCase struct {
+	name   string
+	source string
+	want   []Token
+}
+
+type errorCase struct {
+	name   string
+	source string
+	want   LexError
+}
+
+// tok builds an expected token, offsets are only compared by tests that set them explicitly
+func tok(kind TokenKind, value string) Token {
+	return Token{Kind: kind, Value: value}
+}
+
+func runTokenCases(t *testing.T, cases []tokenCase) {
+	t.Helper()
+
+	for _, tc := range cases {
+		t.Run(tc.name, func(t *testing.T) {
+			tokens, err := Tokenize(tc.source)
+			if err != nil {
+				t.Fatalf("Tokenize(%q) returned error: %v", tc.source, err)
+			}
+
+			got := make([]Token, len(tokens))
+			for i, token := range tokens {
+				got[i] = tok(token.Kind, token.Value)
+			}
+			want := append(append([]Token{}, tc.want...), tok(EOF, "EOF"))
+
+			if !reflect.DeepEqual(got, want) {
+				t.Errorf("Tokenize(%q)\n got: %v\nwant: %v", tc.source, got, want)
+			}
+		})
+	}
+}
+
+func runErrorCases(t *testing.T, cases []errorCase) {
+	t.Helper()
+
+	for _, tc := range cases {
+		t.Run(tc.name, func(t *testing.T) {
+			tokens, err := Tokenize(tc.source)
+
+			var lexErr *LexError
+			if !errors.As(err, &lexErr) {
+				t.Fatalf("Tokenize(%q) = %v, %v; want a *LexError", tc.source, tokens, err)
+			}
+			if *lexErr != tc.want {
+				t.Errorf("Tokenize(%q) error = %q, want %q", tc.source, lexErr.Error(), tc.want.Error())
+			}
+		})
+	}
+}
+
+func TestNumbers(t *testing.T) {
+	runTokenCases(t, []tokenCase{
+		{"integer", "42", []Token{tok(NUMBER, "42")}},
+		{"float", "3.14", []Token{tok(NUMBER, "3.14")}},
+		{"float at end of input", "let x = 2.5", []Token{
+			tok(LET, "let"), tok(IDENTIFIER, "x"), tok(ASSIGNMENT, "="), tok(NUMBER, "2.5"),
+		}},
+		{"range", "0..10", []Token{tok(NUMBER, "0"), tok(DOT_DOT, ".."), tok(NUMBER, "10")}},
+		{"inclusive range", "1..=5", []Token{tok(NUMBER, "1"), tok(DOT_DOT_EQUALS, "..="), tok(NUMBER, "5")}},
+		{"float range", "1.5..2", []Token{tok(NUMBER, "1.5"), tok(DOT_DOT, ".."), tok(NUMBER, "2")}},
+		{"number then operator", "7%2", []Token{tok(NUMBER, "7"), tok(PERCENT, "%"), tok(NUMBER, "2")}},
+	})
+}
+
+func TestNumberErrors(t *testing.T) {
+	runErrorCases(t, []errorCase{
+		{"second decimal point", "1.2.3", LexError{1, 4, 3, "unexpected second decimal point"}},
+		{"second decimal point on a later line", "let a = 1;\nlet b = 10.25.5;", LexError{2, 14, 24, "unexpected second decimal point"}},
+		{"letters after a number", "123abc", LexError{1, 4, 3, "unexpected character 'a' after number"}},
+		{"underscore after a float", "1.5_", LexError{1, 4, 3, "unexpected character '_' after number"}},
+	})
+}
+
+func TestByteOrderMark(t *testing.T) {
+	source := "\uFEFFlet x"
+
+	tokens, err := Tokenize(source)
+	if err != nil {
+		t.Fatalf("Tokenize(%q) returned error: %v", source, err)
+	}
+
+	want := []Token{
+		NewToken(LET, "let", 3, 6),
+		NewToken(IDENTIFIER, "x", 7, 8),
+		NewToken(EOF, "EOF", 8, 8),
+	}
+	if !reflect.DeepEqual(tokens, want) {
+		t.Errorf("Tokenize(%q)\n got: %v\nwant: %v", source, tokens, want)
+	}
+
+	runErrorCases(t, []errorCase{
+		{"column ignores the byte order mark", "\uFEFF1.2.3", LexError{1, 4, 6, "unexpected second decimal point"}},
+	})
+}
+
+func TestUnusualCharacters(t *testing.T) {
+	runErrorCases(t, []errorCase{
+		{"smart quotes", "let a = 1;\nprint(\u201Chi\u201D);", LexError{2, 7, 17, "unexpected left smart quote (U+201C), replace it with a straight double quote (\")"}},
+		{"smart single quote", "let s = \u2019a", LexError{1, 9, 8, "unexpected right smart single quote (U+2019), replace it with a straight single quote (')"}},
+		{"non-breaking space", "let\u00A0x", LexError{1, 4, 3, "unexpected non-breaking space (U+00A0), replace it with a regular space"}},
+		{"minus sign", "let x = \u22125;", LexError{1, 9, 8, "unexpected minus sign (U+2212), replace it with a hyphen (-)"}},
+		{"invalid UTF-8", "let \xff", LexError{1, 5, 4, "invalid UTF-8 byte 0xFF"}},
+	})
+}
+
+func TestStringEscapes(t *testing.T) {
+	runTokenCases(t, []tokenCase{
+		{"newline", `"a\nb"`, []Token{tok(STRING, "a\nb")}},
+		{"tab", `"a\tb"`, []Token{tok(STRING, "a\tb")}},
+		{"carriage return", `"a\rb"`, []Token{tok(STRING, "a\rb")}},
+		{"backslash", `"a\\b"`, []Token{tok(STRING, `a\b`)}},
+		{"double quote", `"say \"hi\""`, []Token{tok(STRING, `say "hi"`)}},
+		{"single quote", `"it\'s"`, []Token{tok(STRING, "it's")}},
+		{"unicode", `"\u{1F600}"`, []Token{tok(STRING, "\U0001F600")}},
+		{"tokens after a string are kept", `print("a\"b");`, []Token{
+			tok(IDENTIFIER, "print"), tok(OPEN_PAREN, "("), tok(STRING, `a"b`), tok(CLOSE_PAREN, ")"), tok(SEMI_COLON, ";"),
+		}},
+	})
+}
+
+func TestStringErrors(t *testing.T) {
+	runErrorCases(t, []errorCase{
+		{"unknown escape", `"\q"`, LexError{1, 2, 1, `unknown escape sequence, backslash followed by 'q' (U+0071)`}},
+		{"unknown non-ASCII escape", `"\é"`, LexError{1, 2, 1, `unknown escape sequence, backslash followed by 'é' (U+00E9)`}},
+		{"escaped newline", "\"a\\\nb\"", LexError{1, 3, 2, `unknown escape sequence, backslash followed by '\n' (U+000A)`}},
+		{"escaped tab", "\"a\\\tb\"", LexError{1, 3, 2, `unknown escape sequence, backslash followed by '\t' (U+0009)`}},
+		{"malformed unicode escape", `"\u41"`, LexError{1, 2, 1, `malformed unicode escape, expected \u{XXXX}`}},
+		{"invalid code point", `"\u{110000}"`, LexError{1, 2, 1, `invalid unicode escape '\u{110000}'`}},
+		{"unterminated", `let s = "abc`, LexError{1, 9, 8, "unterminated string literal"}},
+		{"trailing backslash", `"a\`, LexError{1, 1, 0, "unterminated string literal"}},
+	})
+}
+
+func TestBlockComments(t *testing.T) {
+	runTokenCases(t, []tokenCase{
+		{"single line", "a /* note */ b", []Token{tok(IDENTIFIER, "a"), tok(IDENTIFIER, "b")}},
+		{"multi-line", "a /* one\ntwo */ b", []Token{tok(IDENTIFIER, "a"), tok(IDENTIFIER, "b")}},
+		{"nested", "a /* x /* y */ z */ b", []Token{tok(IDENTIFIER, "a"), tok(IDENTIFIER, "b")}},
+		{"empty", "/**/ a", []Token{tok(IDENTIFIER, "a")}},
+		{"next to a division", "a /*c*// b", []Token{tok(IDENTIFIER, "a"), tok(SLASH, "/"), tok(IDENTIFIER, "b")}},
+	})
+
+	runErrorCases(t, []errorCase{
+		{"unterminated", "a /* never closed", LexError{1, 3, 2, "unterminated block comment"}},
+		{"unterminated on a later line", "a\n  /* b", LexError{2, 3, 4, "unterminated block comment"}},
+		{"nested comment left open", "a /* outer /* inner */", LexError{1, 3, 2, "unterminated block comment"}},
+		{"slash star slash", "/*/ a", LexError{1, 1, 0, "unterminated block comment"}},
+	})
+}
+
+func TestKeywordsMatchWholeWords(t *testing.T) {
+	runTokenCases(t, []tokenCase{
+		{"keyword prefixes", "letter formatted iffy whiled matches", []Token{
+			tok(IDENTIFIER, "letter"), tok(IDENTIFIER, "formatted"), tok(IDENTIFIER, "iffy"),
+			tok(IDENTIFIER, "whiled"), tok(IDENTIFIER, "matches"),
+		}},
+		{"keywords", "let if while match", []Token{
+			tok(LET, "let"), tok(IF, "if"), tok(WHILE, "while"), tok(MATCH, "match"),
+		}},
+		{"declaration using a keyword prefix", "let letter = formula;", []Token{
+			tok(LET, "let"), tok(IDENTIFIER, "letter"), tok(ASSIGNMENT, "="), tok(IDENTIFIER, "formula"), tok(SEMI_COLON, ";"),
+		}},
+	})
+}
+
+func TestUnrecognisedCharacters(t *testing.T) {
+	runErrorCases(t, []errorCase{
+		{"printable", "let a = @b;", LexError{1, 9, 8, `unrecognised character '@' (U+0040)`}},
+		{"zero-width space", "let a\u200B = 1;", LexError{1, 6, 5, `unrecognised character '\u200b' (U+200B)`}},
+		{"NUL", "a\x00", LexError{1, 2, 1, `unrecognised character '\x00' (U+0000)`}},
+	})
+}
+
+func TestRender(t *testing.T) {
+	tests := []struct {
+		name   string
+		source string
+		want   string
+	}{
+		{"number", "1.2.3", "main.kdn:1:4: error: unexpected second decimal point"},
+		{"later line", "let a = 1;\n  /* b", "main.kdn:2:3: error: unterminated block comment"},
+		{"escaped newline stays on one line", "\"a\\\nb\"", `main.kdn:1:3: error: unknown escape sequence, backslash followed by '\n' (U+000A)`},
+	}
+
+	for _, tc := range tests {
+		t.Run(tc.name, func(t *testing.T) {
+			_, err := Tokenize(tc.source)
+
+			var lexErr *LexError
+			if !errors.As(err, &lexErr) {
+				t.Fatalf("Tokenize(%q) error = %v, want a *LexError", tc.source, err)
+			}
+			if got := lexErr.Render("main.kdn"); got != tc.want {
+				t.Errorf("Render() = %q, want %q", got, tc.want)
+			}
+		})
+	}
+}
+
+func TestCompoundAssignment(t *testing.T) {
+	runTokenCases(t, []tokenCase{
+		{"star equals", "a *= 2", []Token{tok(IDENTIFIER, "a"), tok(STAR_EQUALS, "*="), tok(NUMBER, "2")}},
+		{"slash equals", "a /= 2", []Token{tok(IDENTIFIER, "a"), tok(SLASH_EQUALS, "/="), tok(NUMBER, "2")}},
+		{"percent equals", "a %= 2", []Token{tok(IDENTIFIER, "a"), tok(PERCENT_EQUALS, "%="), tok(NUMBER, "2")}},
+		{"slash equals before a block comment", "a /=/* c */2", []Token{tok(IDENTIFIER, "a"), tok(SLASH_EQUALS, "/="), tok(NUMBER, "2")}},
+		{"line comment wins over slash equals", "a//=2", []Token{tok(IDENTIFIER, "a")}},
+	})
+}
+
+func TestMatchTokens(t *testing.T) {
+	runTokenCases(t, []tokenCase{
+		{"match arms", "match x { 1..=5 => { }, _ => { } }", []Token{
+			tok(MATCH, "match"), tok(IDENTIFIER, "x"), tok(OPEN_CURLY, "{"),
+			tok(NUMBER, "1"), tok(DOT_DOT_EQUALS, "..="), tok(NUMBER, "5"), tok(FAT_ARROW, "=>"), tok(OPEN_CURLY, "{"), tok(CLOSE_CURLY, "}"), tok(COMMA, ","),
+			tok(IDENTIFIER, "_"), tok(FAT_ARROW, "=>"), tok(OPEN_CURLY, "{"), tok(CLOSE_CURLY, "}"),
+			tok(CLOSE_CURLY, "}"),
+		}},
+		{"equals before fat arrow", "a ==> b", []Token{tok(IDENTIFIER, "a"), tok(EQUALS, "=="), tok(GREATER, ">"), tok(IDENTIFIER, "b")}},
+		{"greater equals", "a >= b", []Token{tok(IDENTIFIER, "a"), tok(GREATER_EQUALS, ">="), tok(IDENTIFIER, "b")}},
+		{"fat arrow before assignment", "=>=", []Token{tok(FAT_ARROW, "=>"), tok(ASSIGNMENT, "=")}},
+	})
+}
+
+func TestStringOffsets(t *testing.T) {
+	// Escapes and multi-byte text make each literal's source length differ from its value
+	source := `"é" "a\n" "\u{1F600}" "ü" end`
+
+	tokens, err := Tokenize(source)
+	if err != nil {
+		t.Fatalf("Tokenize(%q) returned error: %v", source, err)
+	}
+
+	want := []Token{
+		NewToken(STRING, "é", 0, 4),
+		NewToken(STRING, "a\n", 5, 10),
+		NewToken(STRING, "\U0001F600", 11, 22),
+		NewToken(STRING, "ü", 23, 27),
+		NewToken(IDENTIFIER, "end", 28, 31),
+		NewToken(EOF, "EOF", 31, 31),
+	}
+	if !reflect.DeepEqual(tokens, want) {
+		t.Errorf("Tokenize(%q)\n got: %v\nwant: %v", source, tokens, want)
+	}
+}
@@ -33,15 +33,20 @@ const (
 
 	DOT
 	DOT_DOT
+	DOT_DOT_EQUALS
 	SEMI_COLON
 	COLON
 	QUESTION
 	COMMA
+	FAT_ARROW
 
 	PLUS_PLUS
 	MINUS_MINUS
 	PLUS_EQUALS
 	MINUS_EQUALS
+	STAR_EQUALS
+	SLASH_EQUALS
+	PERCENT_EQUALS
 
 	PLUS
 	DASH
@@ -57,37 +62,53 @@ const (
 	IMPORT
 	FROM
 	FN
+	RETURN
 	IF
 	ELSE
+	MATCH
 	FOREACH
 	WHILE
 	FOR
+	LOOP
+	BREAK
+	CONTINUE
 	EXPORT
 	TYPEOF
 	IN
+	AS
+	NULL
 )
 
 var reserved_lu = map[string]TokenKind{
-	"let":     LET,
-	"const":   CONST,
-	"class":   CLASS,
-	"new":     NEW,
-	"import":  IMPORT,
-	"from":    FROM,
-	"fn":      FN,
-	"if":      IF,
-	"else":    ELSE,
-	"foreach": FOREACH,
-	"while":   WHILE,
-	"for":     FOR,
-	"export":  EXPORT,
-	"typeof":  TYPEOF,
-	"in":      IN,
+	"let":      LET,
+	"const":    CONST,
+	"class":    CLASS,
+	"new":      NEW,
+	"import":   IMPORT,
+	"from":     FROM,
+	"fn":       FN,
+	"return":   RETURN,
+	"if":       IF,
+	"else":     ELSE,
+	"match":    MATCH,
+	"foreach":  FOREACH,
+	"while":    WHILE,
+	"for":      FOR,
+	"loop":     LOOP,
+	"break":    BREAK,
+	"continue": CONTINUE,
+	"export":   EXPORT,
+	"typeof":   TYPEOF,
+	"in":       IN,
+	"as":       AS,
+	"null":     NULL,
 }
 
 type Token struct {
 	Kind  TokenKind
 	Value string
+	Start int // byte offset of the token in the source
+	End   int // byte offset just past the token
 }
 
 func (token Token) isOneOfMany(expectedToken ...TokenKind) bool {
@@ -107,10 +128,12 @@ func (token Token) Debug() {
 	}
 }
 
-func NewToken(kind TokenKind, value string) Token {
+func NewToken(kind TokenKind, value string, start int, end int) Token {
 	return Token{
 		Kind:  kind,
 		Value: value,
+		Start: start,
+		End:   end,
 	}
 }
 
@@ -160,6 +183,8 @@ func TokenKindString(kind TokenKind) string {
 		return "dot"
 	case DOT_DOT:
 		return "dot_dot"
+	case DOT_DOT_EQUALS:
+		return "dot_dot_equals"
 	case SEMI_COLON:
 		return "semicolon"
 	case COLON:
@@ -168,6 +193,8 @@ func TokenKindString(kind TokenKind) string {
 		return "question"
 	case COMMA:
 		return "comma"
+	case FAT_ARROW:
+		return "fat_arrow"
 	case PLUS_PLUS:
 		return "plus_plus"
 	case MINUS_MINUS:
@@ -176,6 +203,12 @@ func TokenKindString(kind TokenKind) string {
 		return "plus_equals"
 	case MINUS_EQUALS:
 		return "minus_equals"
+	case STAR_EQUALS:
+		return "star_equals"
+	case SLASH_EQUALS:
+		return "slash_equals"
+	case PERCENT_EQUALS:
+		return "percent_equals"
 	case PLUS:
 		return "plus"
 	case DASH:
@@ -200,22 +233,36 @@ func TokenKindString(kind TokenKind) string {
 		return "from"
 	case FN:
 		return "fn"
+	case RETURN:
+		return "return"
 	case IF:
 		return "if"
 	case ELSE:
 		return "else"
+	case MATCH:
+		return "match"
 	case FOREACH:
 		return "foreach"
 	case WHILE:
 		return "while"
 	case FOR:
 		return "for"
+	case LOOP:
+		return "loop"
+	case BREAK:
+		return "break"
+	case CONTINUE:
+		return "continue"
 	case EXPORT:
 		return "export"
 	case TYPEOF:
 		return "typeof"
 	case IN:
 		return "in"
+	case AS:
+		return "as"
+	case NULL:
+		return "null"
 	default:
 		return "unknown"
 	}